@@ -1,16 +1,17 @@
 use anyhow::anyhow;
 use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub enum Date {
-    Timestamp(u64),
+    Timestamp(u64, Tz),
     Human(String),
 }
 
 impl Default for Date {
     fn default() -> Self {
-        Self::Timestamp(0)
+        Self::Timestamp(0, chrono_tz::UTC)
     }
 }
 
@@ -20,9 +21,9 @@ impl Serialize for Date {
         S: serde::Serializer,
     {
         let s = match self {
-            Date::Timestamp(ts) => {
-                // Convert the timestamp to a UTC datetime and format it.
-                timestamp_to_date(*ts * 1000)
+            Date::Timestamp(ts, tz) => {
+                // Convert the timestamp to a datetime in the exchange's timezone and format it.
+                timestamp_to_date_tz(*ts * 1000, *tz)
                     .map_err(|_| serde::ser::Error::custom("Error converting timestamp to human"))?
             }
             Date::Human(s) => s.clone(),
@@ -41,35 +42,169 @@ impl<'de> Deserialize<'de> for Date {
 
         let date = date_to_timestamp(&s)
             .map_err(|_| serde::de::Error::custom("Error converting date string to timestamp"))?;
-        Ok(Self::Timestamp(date.max(0) as u64))
+        // The serialized string carries no timezone, so assume UTC; this mirrors the
+        // pre-timezone-aware behavior and keeps deserialization a pure function of `s`.
+        Ok(Self::Timestamp(date.max(0) as u64, chrono_tz::UTC))
     }
 }
 
-/// Converts timestamp to date format in MM D, YYYY (Dec 28, 2005)
-fn timestamp_to_date(timestamp: u64) -> anyhow::Result<String> {
+/// Converts timestamp to date format in MM D, YYYY (Dec 28, 2005), rendered in `tz`
+fn timestamp_to_date_tz(timestamp: u64, tz: Tz) -> anyhow::Result<String> {
     let date = DateTime::from_timestamp_millis(timestamp as i64)
-        .ok_or_else(|| anyhow!("Error converting timestamp to date"))?;
+        .ok_or_else(|| anyhow!("Error converting timestamp to date"))?
+        .with_timezone(&tz);
 
     Ok(date.format("%b %-d, %Y").to_string())
 }
 
-/// Converts date format in MM D, YYYY (Dec 28, 2005) to timestamp
+/// Converts date format in MM D, YYYY (Dec 28, 2005) to a timestamp, treating it as UTC midnight
 pub fn date_string_to_timestamp(date_str: &str) -> anyhow::Result<i64> {
+    date_string_to_timestamp_tz(date_str, chrono_tz::UTC)
+}
+
+/// Converts date format in MM D, YYYY (Dec 28, 2005) to a timestamp, treating it as midnight
+/// local to `tz`
+pub fn date_string_to_timestamp_tz(date_str: &str, tz: Tz) -> anyhow::Result<i64> {
     let naive_date = NaiveDate::parse_from_str(date_str, "%b %-d,%Y")?;
-    let datetime = Utc.from_utc_datetime(&naive_date.and_hms_opt(0, 0, 0).unwrap());
+    let datetime = tz
+        .from_local_datetime(&naive_date.and_hms_opt(0, 0, 0).unwrap())
+        .single()
+        .ok_or_else(|| anyhow!("'{date_str}' midnight is ambiguous or invalid in timezone {tz}"))?;
     Ok(datetime.timestamp())
 }
 
-/// Converts date format in YYYY-MM-DD (2005-12-28) to timestamp
+/// Converts a loosely-formatted date (e.g. YYYY-MM-DD, MM/DD/YYYY, "Dec 28, 2005") to a
+/// timestamp, treating it as UTC midnight
 pub fn date_to_timestamp(date_str: &str) -> anyhow::Result<i64> {
-    let naive_date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?;
-    let datetime = Utc.from_utc_datetime(&naive_date.and_hms_opt(0, 0, 0).unwrap());
+    date_to_timestamp_tz(date_str, chrono_tz::UTC)
+}
+
+/// Converts a loosely-formatted date to a timestamp, treating it as midnight local to `tz`
+pub fn date_to_timestamp_tz(date_str: &str, tz: Tz) -> anyhow::Result<i64> {
+    let naive_date = parse_flexible_date(date_str)?;
+    let datetime = tz
+        .from_local_datetime(&naive_date.and_hms_opt(0, 0, 0).unwrap())
+        .single()
+        .ok_or_else(|| anyhow!("'{date_str}' midnight is ambiguous or invalid in timezone {tz}"))?;
     Ok(datetime.timestamp())
 }
 
-/// Converts date format in YYYY-MM-DD (2005-12-28) to human readable date (Dec 28, 2005)
+/// Resolves an optional IANA timezone name (e.g. "America/New_York") to a `chrono_tz::Tz`,
+/// defaulting to UTC when no name is given so existing unconditional-UTC behavior is preserved.
+pub fn resolve_timezone(name: Option<&str>) -> anyhow::Result<Tz> {
+    match name {
+        None => Ok(chrono_tz::UTC),
+        Some(name) => name.parse::<Tz>().map_err(|_| {
+            anyhow!(
+                "Invalid timezone '{name}': must be a valid IANA timezone identifier (e.g. 'America/New_York')"
+            )
+        }),
+    }
+}
+
+/// Output format for a [`Date::Timestamp`] when writing it out, selected via the CLI's
+/// `--date-format` flag and threaded into [`crate::add_to_file`]. Defaults to `HumanReadable`,
+/// matching `Date`'s original `%b %-d, %Y` serialization.
+#[derive(Debug, Clone, Copy, Default, clap::ValueEnum)]
+pub enum DateFormat {
+    #[default]
+    HumanReadable,
+    Iso8601,
+    UnixSeconds,
+}
+
+/// A [`Date`] rendered according to a [`DateFormat`]: text for `HumanReadable`/`Iso8601`, or a
+/// raw integer for `UnixSeconds` so JSON output stays a number rather than a numeric string.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormattedDate {
+    Text(String),
+    Seconds(i64),
+}
+
+impl Serialize for FormattedDate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            FormattedDate::Text(s) => serializer.serialize_str(s),
+            FormattedDate::Seconds(secs) => serializer.serialize_i64(*secs),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FormattedDate {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FormattedDateVisitor;
+
+        impl serde::de::Visitor<'_> for FormattedDateVisitor {
+            type Value = FormattedDate;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a date string or a Unix-seconds integer")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(FormattedDate::Text(v.to_string()))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(FormattedDate::Seconds(v))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(FormattedDate::Seconds(v as i64))
+            }
+        }
+
+        deserializer.deserialize_any(FormattedDateVisitor)
+    }
+}
+
+/// Renders `date` as a [`FormattedDate`] according to `format`. [`Date::Human`] values are
+/// already text and pass through unchanged regardless of `format`.
+pub fn format_date(date: &Date, format: DateFormat) -> anyhow::Result<FormattedDate> {
+    match date {
+        Date::Human(s) => Ok(FormattedDate::Text(s.clone())),
+        Date::Timestamp(ts, tz) => match format {
+            DateFormat::HumanReadable => {
+                timestamp_to_date_tz(*ts * 1000, *tz).map(FormattedDate::Text)
+            }
+            DateFormat::Iso8601 => DateTime::from_timestamp_millis((*ts * 1000) as i64)
+                .ok_or_else(|| anyhow!("Error converting timestamp to date"))
+                .map(|dt| FormattedDate::Text(dt.with_timezone(tz).format("%Y-%m-%d").to_string())),
+            DateFormat::UnixSeconds => Ok(FormattedDate::Seconds(*ts as i64)),
+        },
+    }
+}
+
+/// Parses a [`FormattedDate`] back into a [`Date::Timestamp`] in `tz`, accepting whichever of
+/// the [`DateFormat`] representations it's given (round-tripping [`format_date`]).
+pub fn parse_formatted_date(value: &FormattedDate, tz: Tz) -> anyhow::Result<Date> {
+    match value {
+        FormattedDate::Seconds(secs) => Ok(Date::Timestamp((*secs).max(0) as u64, tz)),
+        FormattedDate::Text(s) => {
+            let ts = date_to_timestamp_tz(s, tz)?;
+            Ok(Date::Timestamp(ts.max(0) as u64, tz))
+        }
+    }
+}
+
+/// Converts a loosely-formatted date to a human readable date (December 28, 2005)
 pub fn human_readable_date(date_str: &str) -> anyhow::Result<String> {
-    let naive_date = NaiveDate::parse_from_str(date_str, "%Y-%m-%d")?;
+    let naive_date = parse_flexible_date(date_str)?;
 
     let formatted_date = format!(
         "{} {}, {}",
@@ -81,20 +216,286 @@ pub fn human_readable_date(date_str: &str) -> anyhow::Result<String> {
     Ok(formatted_date)
 }
 
+/// Month name/abbreviation lookup, used by [`parse_flexible_date`] to recognize alphabetic tokens.
+const MONTHS: &[(&str, u32)] = &[
+    ("jan", 1),
+    ("january", 1),
+    ("feb", 2),
+    ("february", 2),
+    ("mar", 3),
+    ("march", 3),
+    ("apr", 4),
+    ("april", 4),
+    ("may", 5),
+    ("jun", 6),
+    ("june", 6),
+    ("jul", 7),
+    ("july", 7),
+    ("aug", 8),
+    ("august", 8),
+    ("sep", 9),
+    ("sept", 9),
+    ("september", 9),
+    ("oct", 10),
+    ("october", 10),
+    ("nov", 11),
+    ("november", 11),
+    ("dec", 12),
+    ("december", 12),
+];
+
+/// Weekday name/abbreviation lookup (Monday = 0 .. Sunday = 6). Weekday tokens (e.g. the "Mon"
+/// in "Mon, Dec 28, 2005") carry no information we need, so the value is only used to recognize
+/// and skip the token.
+const WEEKDAYS: &[(&str, u32)] = &[
+    ("mon", 0),
+    ("monday", 0),
+    ("tue", 1),
+    ("tues", 1),
+    ("tuesday", 1),
+    ("wed", 2),
+    ("wednesday", 2),
+    ("thu", 3),
+    ("thur", 3),
+    ("thurs", 3),
+    ("thursday", 3),
+    ("fri", 4),
+    ("friday", 4),
+    ("sat", 5),
+    ("saturday", 5),
+    ("sun", 6),
+    ("sunday", 6),
+];
+
+#[derive(Debug, Clone, Copy)]
+enum DateToken<'a> {
+    Digits(&'a str),
+    Alpha(&'a str),
+}
+
+/// Splits `input` into runs of digits and runs of alphabetic characters, discarding whitespace
+/// and punctuation, which carry no information for [`parse_flexible_date`].
+fn tokenize_date(input: &str) -> Vec<DateToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_ascii_digit() {
+            let mut end = start + c.len_utf8();
+            chars.next();
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_ascii_digit() {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(DateToken::Digits(&input[start..end]));
+        } else if c.is_alphabetic() {
+            let mut end = start + c.len_utf8();
+            chars.next();
+            while let Some(&(i, c)) = chars.peek() {
+                if !c.is_alphabetic() {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            tokens.push(DateToken::Alpha(&input[start..end]));
+        } else {
+            chars.next();
+        }
+    }
+
+    tokens
+}
+
+/// Resolves a two-digit year to a four-digit one: `YY <= current_year % 100` maps to `2000 + YY`,
+/// otherwise `1900 + YY`. Years spelled out with three or more digits are taken literally.
+fn resolve_year(value: i32, digits: usize) -> i32 {
+    if digits <= 2 {
+        let current_year_2digit = Utc::now().year() % 100;
+        if value <= current_year_2digit {
+            2000 + value
+        } else {
+            1900 + value
+        }
+    } else {
+        value
+    }
+}
+
+/// Dayfirst/yearfirst configuration for resolving ambiguous all-numeric dates (e.g. `10/09/2003`).
+/// Defaults match most US-style input: `dayfirst = false`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateParseOptions {
+    pub dayfirst: bool,
+    pub yearfirst: bool,
+}
+
+/// Tolerant, multi-format date parser in the style of Python's `dateutil.parser` ("dtparse").
+///
+/// Accepts inputs such as `2005-12-28`, `2005/12/28`, `28-12-2005`, `Dec 28, 2005` or
+/// `25 Sep 2003`. Uses [`DateParseOptions::default`] to resolve ambiguous all-numeric dates;
+/// use [`parse_flexible_date_with`] to override that behavior.
+pub fn parse_flexible_date(date_str: &str) -> anyhow::Result<NaiveDate> {
+    parse_flexible_date_with(date_str, &DateParseOptions::default())
+}
+
+/// Same as [`parse_flexible_date`], but with explicit control over how ambiguous all-numeric
+/// dates (e.g. `10/09/2003`) are resolved.
+pub fn parse_flexible_date_with(
+    date_str: &str,
+    opts: &DateParseOptions,
+) -> anyhow::Result<NaiveDate> {
+    let mut year: Option<i32> = None;
+    let mut month: Option<u32> = None;
+    let mut day: Option<u32> = None;
+
+    for token in tokenize_date(date_str) {
+        match token {
+            DateToken::Alpha(word) => {
+                let lower = word.to_ascii_lowercase();
+                if let Some((_, m)) = MONTHS.iter().find(|(name, _)| *name == lower) {
+                    if month.is_some() {
+                        return Err(anyhow!(
+                            "Could not parse date '{date_str}': more than one month name found"
+                        ));
+                    }
+                    month = Some(*m);
+                } else if WEEKDAYS.iter().any(|(name, _)| *name == lower) {
+                    // Weekday names are informational only (e.g. "Mon, Dec 28, 2005").
+                    continue;
+                } else {
+                    return Err(anyhow!(
+                        "Could not parse date '{date_str}': unrecognized word '{word}'"
+                    ));
+                }
+            }
+            DateToken::Digits(digits) => {
+                let value: i32 = digits.parse()?;
+
+                if digits.len() == 4 || value > 31 {
+                    if year.is_some() {
+                        return Err(anyhow!(
+                            "Could not parse date '{date_str}': more than one year candidate found"
+                        ));
+                    }
+                    year = Some(resolve_year(value, digits.len()));
+                    continue;
+                }
+
+                if month.is_some() && day.is_none() {
+                    day = Some(value as u32);
+                } else if day.is_some() && month.is_none() && value <= 12 {
+                    month = Some(value as u32);
+                } else if month.is_none() && day.is_none() {
+                    if opts.yearfirst && year.is_none() {
+                        year = Some(resolve_year(value, digits.len()));
+                    } else if opts.dayfirst {
+                        day = Some(value as u32);
+                    } else if value <= 12 {
+                        month = Some(value as u32);
+                    } else {
+                        // Can't be a month, so dtparse's rule applies: it's the day.
+                        day = Some(value as u32);
+                    }
+                } else if month.is_some() && day.is_some() && year.is_none() {
+                    year = Some(resolve_year(value, digits.len()));
+                } else {
+                    return Err(anyhow!(
+                        "Could not parse date '{date_str}': too many numeric fields"
+                    ));
+                }
+            }
+        }
+    }
+
+    let (year, month, day) = match (year, month, day) {
+        (Some(year), Some(month), Some(day)) => (year, month, day),
+        _ => {
+            return Err(anyhow!(
+                "Could not parse date '{date_str}': unable to resolve year, month and day"
+            ))
+        }
+    };
+
+    NaiveDate::from_ymd_opt(year, month, day).ok_or_else(|| {
+        anyhow!("Could not parse date '{date_str}': {year}-{month}-{day} is not a valid date")
+    })
+}
+
 #[cfg(test)]
 mod test {
+    use chrono::Utc;
+
     use super::*;
 
     #[test]
     fn test_serialization_from_timestamp() -> anyhow::Result<()> {
         let dt = Utc.with_ymd_and_hms(2005, 12, 28, 0, 0, 0).unwrap();
-        let date = Date::Timestamp(dt.timestamp() as u64);
+        let date = Date::Timestamp(dt.timestamp() as u64, chrono_tz::UTC);
         let serialized = serde_json::to_string(&date)?;
 
         assert_eq!(serialized, "\"Dec 28, 2005\"");
         Ok(())
     }
 
+    #[test]
+    fn test_serialization_from_timestamp_in_timezone() -> anyhow::Result<()> {
+        // Midnight UTC on the 28th is still the 27th in New York, so the rendered
+        // human date must shift a day when the stored timezone isn't UTC.
+        let dt = Utc.with_ymd_and_hms(2005, 12, 28, 0, 0, 0).unwrap();
+        let date = Date::Timestamp(dt.timestamp() as u64, chrono_tz::America::New_York);
+        let serialized = serde_json::to_string(&date)?;
+
+        assert_eq!(serialized, "\"Dec 27, 2005\"");
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_timezone() -> anyhow::Result<()> {
+        assert_eq!(resolve_timezone(None)?, chrono_tz::UTC);
+        assert_eq!(
+            resolve_timezone(Some("America/New_York"))?,
+            chrono_tz::America::New_York
+        );
+        assert!(resolve_timezone(Some("Not/AZone")).is_err());
+        Ok(())
+    }
+
+    fn round_trip(ts: u64, format: DateFormat) -> anyhow::Result<u64> {
+        let date = Date::Timestamp(ts, chrono_tz::UTC);
+        let formatted = format_date(&date, format)?;
+        let serialized = serde_json::to_string(&formatted)?;
+        let deserialized: FormattedDate = serde_json::from_str(&serialized)?;
+        match parse_formatted_date(&deserialized, chrono_tz::UTC)? {
+            Date::Timestamp(round_tripped, _) => Ok(round_tripped),
+            Date::Human(_) => panic!("expected Date::Timestamp"),
+        }
+    }
+
+    #[test]
+    fn test_date_format_round_trip_human_readable() -> anyhow::Result<()> {
+        let ts = Utc.with_ymd_and_hms(2005, 12, 28, 0, 0, 0).unwrap().timestamp() as u64;
+        assert_eq!(round_trip(ts, DateFormat::HumanReadable)?, ts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_format_round_trip_iso8601() -> anyhow::Result<()> {
+        let ts = Utc.with_ymd_and_hms(2005, 12, 28, 0, 0, 0).unwrap().timestamp() as u64;
+        assert_eq!(round_trip(ts, DateFormat::Iso8601)?, ts);
+        Ok(())
+    }
+
+    #[test]
+    fn test_date_format_round_trip_unix_seconds() -> anyhow::Result<()> {
+        let ts = Utc.with_ymd_and_hms(2005, 12, 28, 0, 0, 0).unwrap().timestamp() as u64;
+        assert_eq!(round_trip(ts, DateFormat::UnixSeconds)?, ts);
+        Ok(())
+    }
+
     #[test]
     fn test_date_to_timestamp_valid() -> anyhow::Result<()> {
         let date_str = "2025-02-09";
@@ -118,4 +519,52 @@ mod test {
         assert_eq!(ts, expected);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_flexible_date_formats() -> anyhow::Result<()> {
+        let expected = NaiveDate::from_ymd_opt(2005, 12, 28).unwrap();
+
+        assert_eq!(parse_flexible_date("2005-12-28")?, expected);
+        assert_eq!(parse_flexible_date("2005/12/28")?, expected);
+        assert_eq!(parse_flexible_date("28-12-2005")?, expected);
+        assert_eq!(parse_flexible_date("Dec 28, 2005")?, expected);
+        assert_eq!(parse_flexible_date("Mon, Dec 28, 2005")?, expected);
+        assert_eq!(parse_flexible_date("28 Dec 2005")?, expected);
+
+        assert_eq!(
+            parse_flexible_date("25 Sep 2003")?,
+            NaiveDate::from_ymd_opt(2003, 9, 25).unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_flexible_date_ambiguous_numeric() -> anyhow::Result<()> {
+        // dayfirst defaults to false, so 10/09/2003 is October 9th.
+        assert_eq!(
+            parse_flexible_date("10/09/2003")?,
+            NaiveDate::from_ymd_opt(2003, 10, 9).unwrap()
+        );
+
+        // With dayfirst enabled, the same input is September 10th.
+        assert_eq!(
+            parse_flexible_date_with(
+                "10/09/2003",
+                &DateParseOptions {
+                    dayfirst: true,
+                    yearfirst: false
+                }
+            )?,
+            NaiveDate::from_ymd_opt(2003, 9, 10).unwrap()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_flexible_date_invalid() {
+        assert!(parse_flexible_date("not a date").is_err());
+        assert!(parse_flexible_date("2005-13-40").is_err());
+    }
 }
@@ -1,10 +1,14 @@
 use std::{borrow::Cow, fmt::Display, future::Future};
 
 use anyhow::anyhow;
-use chrono::{Local, NaiveDate};
+use chrono::Local;
+use chrono_tz::Tz;
 use clap::Subcommand;
 use csv::WriterBuilder;
-use date_util::{date_string_to_timestamp, date_to_timestamp, human_readable_date, Date};
+use date_util::{
+    date_string_to_timestamp_tz, date_to_timestamp_tz, format_date, human_readable_date,
+    parse_flexible_date, Date, DateFormat, FormattedDate,
+};
 use reqwest::{
     header::{HeaderMap, HeaderName, HeaderValue},
     Response,
@@ -29,8 +33,8 @@ pub struct OHLCV {
 }
 
 impl OHLCV {
-    pub fn insert(&mut self, sli: [f64; 7]) {
-        self.date = Date::Timestamp(sli[0] as u64);
+    pub fn insert(&mut self, sli: [f64; 7], tz: Tz) {
+        self.date = Date::Timestamp(sli[0] as u64, tz);
         self.open = sli[1];
         self.high = sli[2];
         self.low = sli[3];
@@ -59,13 +63,29 @@ impl Display for Frequency {
     }
 }
 
+/// Run context threaded through client/parsing/output so the exchange's timezone is applied
+/// consistently instead of unconditional UTC. Defaults to UTC, preserving existing behavior.
+#[derive(Debug, Clone, Copy)]
+pub struct Context {
+    pub tz: Tz,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self {
+            tz: chrono_tz::UTC,
+        }
+    }
+}
+
 pub fn compose_client(
     ticker: &str,
     from: &str,
     end: Option<&str>,
     frequency: Frequency,
+    ctx: &Context,
 ) -> anyhow::Result<impl Future<Output = Result<Response, reqwest::Error>> + Send> {
-    let start_date = date_to_timestamp(from)?.to_string();
+    let start_date = date_to_timestamp_tz(from, ctx.tz)?.to_string();
 
     let end = if let Some(end_date) = end {
         end_date.to_string()
@@ -80,7 +100,7 @@ pub fn compose_client(
         frequency
     );
 
-    let end_date = date_to_timestamp(&end)?.to_string();
+    let end_date = date_to_timestamp_tz(&end, ctx.tz)?.to_string();
 
     let base_url = format!("https://finance.yahoo.com/quote/{}/history", ticker);
 
@@ -118,6 +138,7 @@ pub fn parse_html(
     freq: Frequency,
     start: &str,
     end: Option<&str>,
+    ctx: &Context,
 ) -> anyhow::Result<Vec<OHLCV>> {
     let fragment = Html::parse_fragment(&html);
     let table_body = Selector::parse("tbody").map_err(|_| anyhow!("Error parsing td"))?;
@@ -132,7 +153,7 @@ pub fn parse_html(
         .next()
         .ok_or_else(|| anyhow!("No tbody tag"))?;
 
-    let capacity = get_array_size_for_frequency(freq, start, end)?;
+    let capacity = get_array_size_for_frequency(freq, start, end);
 
     let mut candlesticks: Vec<OHLCV> = if let Some(cap) = capacity {
         Vec::with_capacity(cap as usize)
@@ -149,7 +170,7 @@ pub fn parse_html(
             .next()
             .map(|d| {
                 let date_in_text = d.inner_html();
-                date_string_to_timestamp(&date_in_text).unwrap_or_default() as f64
+                date_string_to_timestamp_tz(&date_in_text, ctx.tz).unwrap_or_default() as f64
             })
             .unwrap_or_default();
 
@@ -170,7 +191,7 @@ pub fn parse_html(
         }
         if !empty {
             let mut ohlcv: OHLCV = OHLCV::default();
-            ohlcv.insert(ohlcv_vec);
+            ohlcv.insert(ohlcv_vec, ctx.tz);
             candlesticks.push(ohlcv);
         }
     }
@@ -178,28 +199,27 @@ pub fn parse_html(
     Ok(candlesticks)
 }
 
-fn get_array_size_for_frequency(
-    freq: Frequency,
-    start: &str,
-    end: &str,
-) -> anyhow::Result<Option<u64>> {
-    let start = NaiveDate::parse_from_str(start, "%Y-%m-%d")?;
-    let end = NaiveDate::parse_from_str(end, "%Y-%m-%d")?;
+fn get_array_size_for_frequency(freq: Frequency, start: &str, end: &str) -> Option<u64> {
+    // This is only a `Vec::with_capacity` hint, so a bad/unparseable date just means
+    // no hint rather than aborting the whole scrape.
+    let start = parse_flexible_date(start).ok()?;
+    let end = parse_flexible_date(end).ok()?;
 
-    let num = match freq {
+    match freq {
         Frequency::Daily => Some((end - start).num_days().max(0) as u64),
         Frequency::Weekly => Some((end - start).num_weeks().max(0) as u64),
         // Can't accurately calculate months
         Frequency::Monthly => None,
-    };
-
-    Ok(num)
+    }
 }
 
 #[derive(Clone, Debug, clap::ValueEnum)]
 pub enum FileFormat {
     CSV,
     JSON,
+    /// Newline-delimited JSON: one compact JSON object per line, written and flushed
+    /// record-by-record so large ranges don't need to be buffered in memory.
+    NDJSON,
 }
 
 impl Display for FileFormat {
@@ -207,6 +227,7 @@ impl Display for FileFormat {
         let format = match self {
             Self::CSV => "csv",
             Self::JSON => "json",
+            Self::NDJSON => "ndjson",
         };
 
         write!(f, "{format}")
@@ -218,12 +239,13 @@ pub async fn retrieve_historical_data(
     start: &str,
     end: Option<&str>,
     frequency: Frequency,
+    ctx: &Context,
 ) -> anyhow::Result<Vec<OHLCV>> {
-    let client = compose_client(ticker, start, end, frequency)?;
+    let client = compose_client(ticker, start, end, frequency, ctx)?;
 
     let data = client.await?.text().await?;
 
-    let parsed_data = parse_html(data, frequency, start, end)?;
+    let parsed_data = parse_html(data, frequency, start, end, ctx)?;
 
     Ok(parsed_data)
 }
@@ -238,11 +260,19 @@ pub fn prepare_file_name<'a>(
     if let Some(name) = file_name {
         Cow::Borrowed(name)
     } else {
+        // Normalize through the flexible parser so filenames stay safe/consistent
+        // regardless of which date format the caller passed in.
+        let normalize = |s: &str| {
+            parse_flexible_date(s)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|_| s.to_string())
+        };
+
         let autoname = format!(
             "yfp_{}_{}_{}_{}_{}",
             ticker,
-            start,
-            end.unwrap_or("today"),
+            normalize(start),
+            end.map_or_else(|| "today".to_string(), normalize),
             frequency,
             Local::now().format("%Y-%m-%d")
         );
@@ -250,26 +280,45 @@ pub fn prepare_file_name<'a>(
     }
 }
 
+/// An [`OHLCV`] record with its date rendered according to the requested [`DateFormat`],
+/// in place of `OHLCV`'s own fixed human-readable `Date` serialization.
+#[derive(Debug, Clone, Serialize)]
+struct OutputRow {
+    date: FormattedDate,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    adj_close: f64,
+    volume: u64,
+}
+
+impl OutputRow {
+    fn new(record: OHLCV, date_format: DateFormat) -> anyhow::Result<Self> {
+        Ok(Self {
+            date: format_date(&record.date, date_format)?,
+            open: record.open,
+            high: record.high,
+            low: record.low,
+            close: record.close,
+            adj_close: record.adj_close,
+            volume: record.volume,
+        })
+    }
+}
+
 pub async fn add_to_file(
-    data: Vec<OHLCV>,
+    data: impl IntoIterator<Item = OHLCV>,
     file_name: &str,
     file_format: FileFormat,
+    date_format: DateFormat,
 ) -> anyhow::Result<()> {
+    let rows = data
+        .into_iter()
+        .map(|record| OutputRow::new(record, date_format));
+
     match file_format {
         FileFormat::CSV => {
-            let mut buf = Vec::new();
-
-            // drop exclusive reference in scope
-            {
-                let mut wtr = WriterBuilder::new().from_writer(&mut buf);
-
-                for record in data {
-                    wtr.serialize(record)?;
-                }
-
-                wtr.flush()?;
-            }
-
             let mut file = tokio::fs::File::options()
                 .create(true)
                 .truncate(true)
@@ -277,12 +326,21 @@ pub async fn add_to_file(
                 .open(format!("{}.csv", file_name))
                 .await?;
 
-            file.write_all(&buf).await?;
+            // csv::Writer needs a std::io::Write, so it serializes into an owned scratch
+            // buffer; that buffer is drained to the async file and cleared after every row
+            // so memory stays bounded to one row rather than the whole file.
+            let mut wtr = WriterBuilder::new().from_writer(Vec::new());
+            for row in rows {
+                wtr.serialize(row?)?;
+                wtr.flush()?;
+
+                let buf = std::mem::take(wtr.get_mut());
+                file.write_all(&buf).await?;
+            }
+
             info!("File saved to {file_name}.csv");
         }
         FileFormat::JSON => {
-            let serialized_data = serde_json::to_string_pretty(&data)?;
-
             let mut file = tokio::fs::File::options()
                 .create(true)
                 .truncate(true)
@@ -290,9 +348,38 @@ pub async fn add_to_file(
                 .open(format!("{}.json", file_name))
                 .await?;
 
-            file.write_all(serialized_data.as_bytes()).await?;
+            file.write_all(b"[\n").await?;
+            for (i, row) in rows.enumerate() {
+                if i > 0 {
+                    file.write_all(b",\n").await?;
+                }
+
+                let serialized = serde_json::to_string_pretty(&row?)?;
+                file.write_all(b"  ").await?;
+                file.write_all(serialized.replace('\n', "\n  ").as_bytes())
+                    .await?;
+            }
+            file.write_all(b"\n]\n").await?;
+
             info!("File saved to {file_name}.json");
         }
+        FileFormat::NDJSON => {
+            let mut file = tokio::fs::File::options()
+                .create(true)
+                .truncate(true)
+                .write(true)
+                .open(format!("{}.ndjson", file_name))
+                .await?;
+
+            for row in rows {
+                let serialized = serde_json::to_string(&row?)?;
+                file.write_all(serialized.as_bytes()).await?;
+                file.write_all(b"\n").await?;
+                file.flush().await?;
+            }
+
+            info!("File saved to {file_name}.ndjson");
+        }
     };
 
     Ok(())
@@ -334,8 +421,14 @@ mod test {
     #[tokio::test]
     async fn test_get_historical_data() -> anyhow::Result<()> {
         let ticker = "VOO";
-        let parsed_data =
-            retrieve_historical_data(ticker, "2020-01-01", None, Frequency::Monthly).await?;
+        let parsed_data = retrieve_historical_data(
+            ticker,
+            "2020-01-01",
+            None,
+            Frequency::Monthly,
+            &Context::default(),
+        )
+        .await?;
         assert!(!parsed_data.is_empty());
 
         // Closing price of VOO on January of 2020 was 273.59
@@ -371,7 +464,7 @@ mod test {
             },
         ];
 
-        add_to_file(data, file_name, FileFormat::CSV).await?;
+        add_to_file(data, file_name, FileFormat::CSV, DateFormat::default()).await?;
 
         let file_path = base_path.with_extension("csv");
         let content = fs::read_to_string(&file_path).await?;
@@ -383,4 +476,47 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_add_file_ndjson() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let base_path = temp_dir.path().join("test_ndjson");
+        let file_name = base_path.to_str().unwrap();
+
+        let data = vec![
+            OHLCV {
+                date: Date::Human("Dec 24, 2020".into()),
+                open: 1.0,
+                high: 2.0,
+                low: 0.5,
+                close: 1.5,
+                adj_close: 1.5,
+                volume: 100,
+            },
+            OHLCV {
+                date: Date::Human("Dec 25, 2020".into()),
+                open: 1.5,
+                high: 2.5,
+                low: 1.0,
+                close: 2.0,
+                adj_close: 2.0,
+                volume: 150,
+            },
+        ];
+
+        add_to_file(data, file_name, FileFormat::NDJSON, DateFormat::default()).await?;
+
+        let file_path = base_path.with_extension("ndjson");
+        let content = fs::read_to_string(&file_path).await?;
+        let lines: Vec<&str> = content.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            serde_json::from_str::<serde_json::Value>(line)?;
+        }
+        assert!(lines[0].contains("Dec 24, 2020"));
+        assert!(lines[1].contains("Dec 25, 2020"));
+
+        Ok(())
+    }
 }
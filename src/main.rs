@@ -5,8 +5,9 @@ use chrono::Local;
 use clap::Parser;
 use tracing::info;
 use yfp::{
-    add_to_file, date_util::human_readable_date, prepare_file_name, retrieve_historical_data,
-    FileFormat, Frequency,
+    add_to_file,
+    date_util::{human_readable_date, resolve_timezone, DateFormat},
+    prepare_file_name, retrieve_historical_data, Context, FileFormat, Frequency,
 };
 
 #[derive(Parser, Debug, Clone)]
@@ -31,6 +32,22 @@ pub struct Cli {
     #[arg(short = 'n', long)]
     file_name: Option<String>,
 
+    #[arg(
+        short = 'z',
+        long,
+        help = "IANA timezone of the exchange (e.g. America/New_York). Defaults to UTC"
+    )]
+    timezone: Option<String>,
+
+    #[arg(
+        short = 'd',
+        long,
+        value_enum,
+        default_value = "human-readable",
+        help = "How to render dates in the output file"
+    )]
+    date_format: DateFormat,
+
     #[command(subcommand)]
     frequency: Frequency,
 }
@@ -40,10 +57,11 @@ impl Display for Cli {
         let today = Local::now().format("%Y-%m-%d").to_string();
         write!(
             f,
-            "\nTicker: {}\n\nStart: {}\n\nEnd: {}\n\nFrequency: {}\n\nFile Name: {}.{}\n\n",
+            "\nTicker: {}\n\nStart: {}\n\nEnd: {}\n\nTimezone: {}\n\nFrequency: {}\n\nFile Name: {}.{}\n\n",
             self.ticker,
             human_readable_date(&self.start).unwrap(),
             human_readable_date(self.end.as_deref().unwrap_or(&today)).unwrap(),
+            self.timezone.as_deref().unwrap_or("UTC"),
             self.frequency,
             &self
                 .file_name
@@ -53,6 +71,7 @@ impl Display for Cli {
                 match self.file_format {
                     FileFormat::CSV => "csv",
                     FileFormat::JSON => "json",
+                    FileFormat::NDJSON => "ndjson",
                 }
             } else {
                 ""
@@ -69,9 +88,18 @@ async fn main() -> anyhow::Result<()> {
 
     info!("{cli}");
 
-    let parsed_data =
-        retrieve_historical_data(&cli.ticker, &cli.start, cli.end.as_deref(), cli.frequency)
-            .await?;
+    let ctx = Context {
+        tz: resolve_timezone(cli.timezone.as_deref())?,
+    };
+
+    let parsed_data = retrieve_historical_data(
+        &cli.ticker,
+        &cli.start,
+        cli.end.as_deref(),
+        cli.frequency,
+        &ctx,
+    )
+    .await?;
 
     let file_name = prepare_file_name(
         &cli.ticker,
@@ -81,7 +109,13 @@ async fn main() -> anyhow::Result<()> {
         cli.file_name.as_deref(),
     );
 
-    add_to_file(parsed_data, file_name.borrow(), cli.file_format).await?;
+    add_to_file(
+        parsed_data,
+        file_name.borrow(),
+        cli.file_format,
+        cli.date_format,
+    )
+    .await?;
 
     Ok(())
 }